@@ -0,0 +1,680 @@
+use crate::{Error, Result};
+
+#[derive(Debug)]
+pub enum Instruction {
+    Nop,
+    AconstNull,
+    IconstM1,
+    Iconst0,
+    Iconst1,
+    Iconst2,
+    Iconst3,
+    Iconst4,
+    Iconst5,
+    Lconst0,
+    Lconst1,
+    Fconst0,
+    Fconst1,
+    Fconst2,
+    Dconst0,
+    Dconst1,
+    Bipush(i8),
+    Sipush(i16),
+    Ldc(usize),
+    LdcW(usize),
+    Ldc2W(usize),
+    Iload(u16),
+    Lload(u16),
+    Fload(u16),
+    Dload(u16),
+    Aload(u16),
+    Iload0,
+    Iload1,
+    Iload2,
+    Iload3,
+    Lload0,
+    Lload1,
+    Lload2,
+    Lload3,
+    Fload0,
+    Fload1,
+    Fload2,
+    Fload3,
+    Dload0,
+    Dload1,
+    Dload2,
+    Dload3,
+    Aload0,
+    Aload1,
+    Aload2,
+    Aload3,
+    Iaload,
+    Laload,
+    Faload,
+    Daload,
+    Aaload,
+    Baload,
+    Caload,
+    Saload,
+    Istore(u16),
+    Lstore(u16),
+    Fstore(u16),
+    Dstore(u16),
+    Astore(u16),
+    Istore0,
+    Istore1,
+    Istore2,
+    Istore3,
+    Lstore0,
+    Lstore1,
+    Lstore2,
+    Lstore3,
+    Fstore0,
+    Fstore1,
+    Fstore2,
+    Fstore3,
+    Dstore0,
+    Dstore1,
+    Dstore2,
+    Dstore3,
+    Astore0,
+    Astore1,
+    Astore2,
+    Astore3,
+    Iastore,
+    Lastore,
+    Fastore,
+    Dastore,
+    Aastore,
+    Bastore,
+    Castore,
+    Sastore,
+    Pop,
+    Pop2,
+    Dup,
+    DupX1,
+    DupX2,
+    Dup2,
+    Dup2X1,
+    Dup2X2,
+    Swap,
+    Iadd,
+    Ladd,
+    Fadd,
+    Dadd,
+    Isub,
+    Lsub,
+    Fsub,
+    Dsub,
+    Imul,
+    Lmul,
+    Fmul,
+    Dmul,
+    Idiv,
+    Ldiv,
+    Fdiv,
+    Ddiv,
+    Irem,
+    Lrem,
+    Frem,
+    Drem,
+    Ineg,
+    Lneg,
+    Fneg,
+    Dneg,
+    Ishl,
+    Lshl,
+    Ishr,
+    Lshr,
+    Iushr,
+    Lushr,
+    Iand,
+    Land,
+    Ior,
+    Lor,
+    Ixor,
+    Lxor,
+    Iinc { index: u16, value: i16 },
+    I2l,
+    I2f,
+    I2d,
+    L2i,
+    L2f,
+    L2d,
+    F2i,
+    F2l,
+    F2d,
+    D2i,
+    D2l,
+    D2f,
+    I2b,
+    I2c,
+    I2s,
+    Lcmp,
+    Fcmpl,
+    Fcmpg,
+    Dcmpl,
+    Dcmpg,
+    Ifeq(i16),
+    Ifne(i16),
+    Iflt(i16),
+    Ifge(i16),
+    Ifgt(i16),
+    Ifle(i16),
+    IfIcmpeq(i16),
+    IfIcmpne(i16),
+    IfIcmplt(i16),
+    IfIcmpge(i16),
+    IfIcmpgt(i16),
+    IfIcmple(i16),
+    IfAcmpeq(i16),
+    IfAcmpne(i16),
+    Goto(i16),
+    Jsr(i16),
+    Ret(u16),
+    TableSwitch {
+        default: i32,
+        low: i32,
+        high: i32,
+        offsets: Vec<i32>,
+    },
+    LookupSwitch {
+        default: i32,
+        pairs: Vec<(i32, i32)>,
+    },
+    Ireturn,
+    Lreturn,
+    Freturn,
+    Dreturn,
+    Areturn,
+    Return,
+    GetStatic(usize),
+    PutStatic(usize),
+    GetField(usize),
+    PutField(usize),
+    InvokeVirtual(usize),
+    InvokeSpecial(usize),
+    InvokeStatic(usize),
+    InvokeInterface { index: usize, count: u8 },
+    InvokeDynamic(usize),
+    New(usize),
+    NewArray(u8),
+    ANewArray(usize),
+    ArrayLength,
+    AThrow,
+    CheckCast(usize),
+    InstanceOf(usize),
+    MonitorEnter,
+    MonitorExit,
+    MultiANewArray { index: usize, dimensions: u8 },
+    IfNull(i16),
+    IfNonNull(i16),
+    GotoW(i32),
+    JsrW(i32),
+    Breakpoint,
+    ImpDep1,
+    ImpDep2,
+}
+
+// A cursor over a `Code` attribute's raw bytes that tracks its position so
+// `tableswitch`/`lookupswitch` can compute their alignment padding relative
+// to the start of the code array.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, pos: 0 }
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        let byte = *self
+            .data
+            .get(self.pos)
+            .ok_or(Error::TruncatedBytecode(self.pos as u32))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn i8(&mut self) -> Result<i8> {
+        Ok(self.u8()? as i8)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        Ok(self.u16()? as i16)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes([
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+        ]))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(self.u32()? as i32)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<()> {
+        for _ in 0..count {
+            self.u8()?;
+        }
+        Ok(())
+    }
+}
+
+// Decodes the raw `code` array of a `Code` attribute into a sequence of
+// instructions paired with the offset (from the start of the array) each
+// one starts at. Branch offsets, local-variable indices and constant-pool
+// indices are decoded per JVMS 6.5; `tableswitch`/`lookupswitch` padding and
+// `wide`-prefixed operands are handled inline.
+pub fn decode(code: &[u8]) -> Result<Vec<(u32, Instruction)>> {
+    let mut reader = Reader::new(code);
+    let mut instructions = Vec::new();
+
+    while reader.pos < code.len() {
+        let offset = reader.pos as u32;
+        let opcode = reader.u8()?;
+
+        let instruction = match opcode {
+            0x00 => Instruction::Nop,
+            0x01 => Instruction::AconstNull,
+            0x02 => Instruction::IconstM1,
+            0x03 => Instruction::Iconst0,
+            0x04 => Instruction::Iconst1,
+            0x05 => Instruction::Iconst2,
+            0x06 => Instruction::Iconst3,
+            0x07 => Instruction::Iconst4,
+            0x08 => Instruction::Iconst5,
+            0x09 => Instruction::Lconst0,
+            0x0a => Instruction::Lconst1,
+            0x0b => Instruction::Fconst0,
+            0x0c => Instruction::Fconst1,
+            0x0d => Instruction::Fconst2,
+            0x0e => Instruction::Dconst0,
+            0x0f => Instruction::Dconst1,
+            0x10 => Instruction::Bipush(reader.i8()?),
+            0x11 => Instruction::Sipush(reader.i16()?),
+            0x12 => Instruction::Ldc(reader.u8()? as usize),
+            0x13 => Instruction::LdcW(reader.u16()? as usize),
+            0x14 => Instruction::Ldc2W(reader.u16()? as usize),
+            0x15 => Instruction::Iload(reader.u8()? as u16),
+            0x16 => Instruction::Lload(reader.u8()? as u16),
+            0x17 => Instruction::Fload(reader.u8()? as u16),
+            0x18 => Instruction::Dload(reader.u8()? as u16),
+            0x19 => Instruction::Aload(reader.u8()? as u16),
+            0x1a => Instruction::Iload0,
+            0x1b => Instruction::Iload1,
+            0x1c => Instruction::Iload2,
+            0x1d => Instruction::Iload3,
+            0x1e => Instruction::Lload0,
+            0x1f => Instruction::Lload1,
+            0x20 => Instruction::Lload2,
+            0x21 => Instruction::Lload3,
+            0x22 => Instruction::Fload0,
+            0x23 => Instruction::Fload1,
+            0x24 => Instruction::Fload2,
+            0x25 => Instruction::Fload3,
+            0x26 => Instruction::Dload0,
+            0x27 => Instruction::Dload1,
+            0x28 => Instruction::Dload2,
+            0x29 => Instruction::Dload3,
+            0x2a => Instruction::Aload0,
+            0x2b => Instruction::Aload1,
+            0x2c => Instruction::Aload2,
+            0x2d => Instruction::Aload3,
+            0x2e => Instruction::Iaload,
+            0x2f => Instruction::Laload,
+            0x30 => Instruction::Faload,
+            0x31 => Instruction::Daload,
+            0x32 => Instruction::Aaload,
+            0x33 => Instruction::Baload,
+            0x34 => Instruction::Caload,
+            0x35 => Instruction::Saload,
+            0x36 => Instruction::Istore(reader.u8()? as u16),
+            0x37 => Instruction::Lstore(reader.u8()? as u16),
+            0x38 => Instruction::Fstore(reader.u8()? as u16),
+            0x39 => Instruction::Dstore(reader.u8()? as u16),
+            0x3a => Instruction::Astore(reader.u8()? as u16),
+            0x3b => Instruction::Istore0,
+            0x3c => Instruction::Istore1,
+            0x3d => Instruction::Istore2,
+            0x3e => Instruction::Istore3,
+            0x3f => Instruction::Lstore0,
+            0x40 => Instruction::Lstore1,
+            0x41 => Instruction::Lstore2,
+            0x42 => Instruction::Lstore3,
+            0x43 => Instruction::Fstore0,
+            0x44 => Instruction::Fstore1,
+            0x45 => Instruction::Fstore2,
+            0x46 => Instruction::Fstore3,
+            0x47 => Instruction::Dstore0,
+            0x48 => Instruction::Dstore1,
+            0x49 => Instruction::Dstore2,
+            0x4a => Instruction::Dstore3,
+            0x4b => Instruction::Astore0,
+            0x4c => Instruction::Astore1,
+            0x4d => Instruction::Astore2,
+            0x4e => Instruction::Astore3,
+            0x4f => Instruction::Iastore,
+            0x50 => Instruction::Lastore,
+            0x51 => Instruction::Fastore,
+            0x52 => Instruction::Dastore,
+            0x53 => Instruction::Aastore,
+            0x54 => Instruction::Bastore,
+            0x55 => Instruction::Castore,
+            0x56 => Instruction::Sastore,
+            0x57 => Instruction::Pop,
+            0x58 => Instruction::Pop2,
+            0x59 => Instruction::Dup,
+            0x5a => Instruction::DupX1,
+            0x5b => Instruction::DupX2,
+            0x5c => Instruction::Dup2,
+            0x5d => Instruction::Dup2X1,
+            0x5e => Instruction::Dup2X2,
+            0x5f => Instruction::Swap,
+            0x60 => Instruction::Iadd,
+            0x61 => Instruction::Ladd,
+            0x62 => Instruction::Fadd,
+            0x63 => Instruction::Dadd,
+            0x64 => Instruction::Isub,
+            0x65 => Instruction::Lsub,
+            0x66 => Instruction::Fsub,
+            0x67 => Instruction::Dsub,
+            0x68 => Instruction::Imul,
+            0x69 => Instruction::Lmul,
+            0x6a => Instruction::Fmul,
+            0x6b => Instruction::Dmul,
+            0x6c => Instruction::Idiv,
+            0x6d => Instruction::Ldiv,
+            0x6e => Instruction::Fdiv,
+            0x6f => Instruction::Ddiv,
+            0x70 => Instruction::Irem,
+            0x71 => Instruction::Lrem,
+            0x72 => Instruction::Frem,
+            0x73 => Instruction::Drem,
+            0x74 => Instruction::Ineg,
+            0x75 => Instruction::Lneg,
+            0x76 => Instruction::Fneg,
+            0x77 => Instruction::Dneg,
+            0x78 => Instruction::Ishl,
+            0x79 => Instruction::Lshl,
+            0x7a => Instruction::Ishr,
+            0x7b => Instruction::Lshr,
+            0x7c => Instruction::Iushr,
+            0x7d => Instruction::Lushr,
+            0x7e => Instruction::Iand,
+            0x7f => Instruction::Land,
+            0x80 => Instruction::Ior,
+            0x81 => Instruction::Lor,
+            0x82 => Instruction::Ixor,
+            0x83 => Instruction::Lxor,
+            0x84 => Instruction::Iinc {
+                index: reader.u8()? as u16,
+                value: reader.i8()? as i16,
+            },
+            0x85 => Instruction::I2l,
+            0x86 => Instruction::I2f,
+            0x87 => Instruction::I2d,
+            0x88 => Instruction::L2i,
+            0x89 => Instruction::L2f,
+            0x8a => Instruction::L2d,
+            0x8b => Instruction::F2i,
+            0x8c => Instruction::F2l,
+            0x8d => Instruction::F2d,
+            0x8e => Instruction::D2i,
+            0x8f => Instruction::D2l,
+            0x90 => Instruction::D2f,
+            0x91 => Instruction::I2b,
+            0x92 => Instruction::I2c,
+            0x93 => Instruction::I2s,
+            0x94 => Instruction::Lcmp,
+            0x95 => Instruction::Fcmpl,
+            0x96 => Instruction::Fcmpg,
+            0x97 => Instruction::Dcmpl,
+            0x98 => Instruction::Dcmpg,
+            0x99 => Instruction::Ifeq(reader.i16()?),
+            0x9a => Instruction::Ifne(reader.i16()?),
+            0x9b => Instruction::Iflt(reader.i16()?),
+            0x9c => Instruction::Ifge(reader.i16()?),
+            0x9d => Instruction::Ifgt(reader.i16()?),
+            0x9e => Instruction::Ifle(reader.i16()?),
+            0x9f => Instruction::IfIcmpeq(reader.i16()?),
+            0xa0 => Instruction::IfIcmpne(reader.i16()?),
+            0xa1 => Instruction::IfIcmplt(reader.i16()?),
+            0xa2 => Instruction::IfIcmpge(reader.i16()?),
+            0xa3 => Instruction::IfIcmpgt(reader.i16()?),
+            0xa4 => Instruction::IfIcmple(reader.i16()?),
+            0xa5 => Instruction::IfAcmpeq(reader.i16()?),
+            0xa6 => Instruction::IfAcmpne(reader.i16()?),
+            0xa7 => Instruction::Goto(reader.i16()?),
+            0xa8 => Instruction::Jsr(reader.i16()?),
+            0xa9 => Instruction::Ret(reader.u8()? as u16),
+            0xaa => {
+                let pad = (4 - (reader.pos % 4)) % 4;
+                reader.skip(pad)?;
+                let default = reader.i32()?;
+                let low = reader.i32()?;
+                let high = reader.i32()?;
+                // Computed in i64 so adversarial low/high (e.g. i32::MIN/MAX)
+                // can't overflow the subtraction before it's validated. Also
+                // capped against the bytes actually left in `code` so a huge
+                // count can't force a multi-gigabyte allocation up front.
+                let count = high as i64 - low as i64 + 1;
+                let max_entries = ((code.len() - reader.pos) / 4) as i64;
+                if !(0..=max_entries).contains(&count) {
+                    return Err(Error::MalformedSwitch(offset));
+                }
+                let mut offsets = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    offsets.push(reader.i32()?);
+                }
+                Instruction::TableSwitch {
+                    default,
+                    low,
+                    high,
+                    offsets,
+                }
+            }
+            0xab => {
+                let pad = (4 - (reader.pos % 4)) % 4;
+                reader.skip(pad)?;
+                let default = reader.i32()?;
+                let npairs = reader.i32()? as i64;
+                let max_pairs = ((code.len() - reader.pos) / 8) as i64;
+                if !(0..=max_pairs).contains(&npairs) {
+                    return Err(Error::MalformedSwitch(offset));
+                }
+                let mut pairs = Vec::with_capacity(npairs as usize);
+                for _ in 0..npairs {
+                    let key = reader.i32()?;
+                    let offset = reader.i32()?;
+                    pairs.push((key, offset));
+                }
+                Instruction::LookupSwitch { default, pairs }
+            }
+            0xac => Instruction::Ireturn,
+            0xad => Instruction::Lreturn,
+            0xae => Instruction::Freturn,
+            0xaf => Instruction::Dreturn,
+            0xb0 => Instruction::Areturn,
+            0xb1 => Instruction::Return,
+            0xb2 => Instruction::GetStatic(reader.u16()? as usize),
+            0xb3 => Instruction::PutStatic(reader.u16()? as usize),
+            0xb4 => Instruction::GetField(reader.u16()? as usize),
+            0xb5 => Instruction::PutField(reader.u16()? as usize),
+            0xb6 => Instruction::InvokeVirtual(reader.u16()? as usize),
+            0xb7 => Instruction::InvokeSpecial(reader.u16()? as usize),
+            0xb8 => Instruction::InvokeStatic(reader.u16()? as usize),
+            0xb9 => {
+                let index = reader.u16()? as usize;
+                let count = reader.u8()?;
+                reader.skip(1)?; // the trailing zero byte
+                Instruction::InvokeInterface { index, count }
+            }
+            0xba => {
+                let index = reader.u16()? as usize;
+                reader.skip(2)?; // the trailing two zero bytes
+                Instruction::InvokeDynamic(index)
+            }
+            0xbb => Instruction::New(reader.u16()? as usize),
+            0xbc => Instruction::NewArray(reader.u8()?),
+            0xbd => Instruction::ANewArray(reader.u16()? as usize),
+            0xbe => Instruction::ArrayLength,
+            0xbf => Instruction::AThrow,
+            0xc0 => Instruction::CheckCast(reader.u16()? as usize),
+            0xc1 => Instruction::InstanceOf(reader.u16()? as usize),
+            0xc2 => Instruction::MonitorEnter,
+            0xc3 => Instruction::MonitorExit,
+            0xc4 => {
+                let widened = reader.u8()?;
+                if widened == 0x84 {
+                    let index = reader.u16()?;
+                    let value = reader.i16()?;
+                    Instruction::Iinc { index, value }
+                } else {
+                    let index = reader.u16()?;
+                    match widened {
+                        0x15 => Instruction::Iload(index),
+                        0x16 => Instruction::Lload(index),
+                        0x17 => Instruction::Fload(index),
+                        0x18 => Instruction::Dload(index),
+                        0x19 => Instruction::Aload(index),
+                        0x36 => Instruction::Istore(index),
+                        0x37 => Instruction::Lstore(index),
+                        0x38 => Instruction::Fstore(index),
+                        0x39 => Instruction::Dstore(index),
+                        0x3a => Instruction::Astore(index),
+                        0xa9 => Instruction::Ret(index),
+                        _ => return Err(Error::UnknownOpcode { opcode, offset }),
+                    }
+                }
+            }
+            0xc5 => {
+                let index = reader.u16()? as usize;
+                let dimensions = reader.u8()?;
+                Instruction::MultiANewArray { index, dimensions }
+            }
+            0xc6 => Instruction::IfNull(reader.i16()?),
+            0xc7 => Instruction::IfNonNull(reader.i16()?),
+            0xc8 => Instruction::GotoW(reader.i32()?),
+            0xc9 => Instruction::JsrW(reader.i32()?),
+            0xca => Instruction::Breakpoint,
+            0xfe => Instruction::ImpDep1,
+            0xff => Instruction::ImpDep2,
+            _ => return Err(Error::UnknownOpcode { opcode, offset }),
+        };
+
+        instructions.push((offset, instruction));
+    }
+
+    Ok(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_tableswitch() {
+        let mut code = vec![0xaa]; // tableswitch
+        code.extend_from_slice(&[0; 3]); // padding to 4-byte alignment
+        code.extend_from_slice(&100i32.to_be_bytes()); // default
+        code.extend_from_slice(&0i32.to_be_bytes()); // low
+        code.extend_from_slice(&2i32.to_be_bytes()); // high
+        for offset in [10i32, 20, 30] {
+            code.extend_from_slice(&offset.to_be_bytes());
+        }
+
+        let instructions = decode(&code).unwrap();
+
+        assert!(matches!(
+            &instructions[0],
+            (0, Instruction::TableSwitch { default: 100, low: 0, high: 2, offsets })
+                if offsets == &[10, 20, 30]
+        ));
+    }
+
+    #[test]
+    fn decodes_lookupswitch() {
+        let mut code = vec![0xab]; // lookupswitch
+        code.extend_from_slice(&[0; 3]); // padding to 4-byte alignment
+        code.extend_from_slice(&100i32.to_be_bytes()); // default
+        code.extend_from_slice(&2i32.to_be_bytes()); // npairs
+        for (key, offset) in [(1i32, 10i32), (2, 20)] {
+            code.extend_from_slice(&key.to_be_bytes());
+            code.extend_from_slice(&offset.to_be_bytes());
+        }
+
+        let instructions = decode(&code).unwrap();
+
+        assert!(matches!(
+            &instructions[0],
+            (0, Instruction::LookupSwitch { default: 100, pairs })
+                if pairs == &[(1, 10), (2, 20)]
+        ));
+    }
+
+    #[test]
+    fn rejects_tableswitch_with_high_below_low() {
+        let mut code = vec![0xaa];
+        code.extend_from_slice(&[0; 3]);
+        code.extend_from_slice(&0i32.to_be_bytes()); // default
+        code.extend_from_slice(&5i32.to_be_bytes()); // low
+        code.extend_from_slice(&0i32.to_be_bytes()); // high < low
+
+        assert!(matches!(decode(&code), Err(Error::MalformedSwitch(0))));
+    }
+
+    #[test]
+    fn rejects_lookupswitch_with_negative_npairs() {
+        let mut code = vec![0xab];
+        code.extend_from_slice(&[0; 3]);
+        code.extend_from_slice(&0i32.to_be_bytes()); // default
+        code.extend_from_slice(&(-1i32).to_be_bytes()); // npairs
+
+        assert!(matches!(decode(&code), Err(Error::MalformedSwitch(0))));
+    }
+
+    #[test]
+    fn rejects_tableswitch_count_exceeding_remaining_bytes() {
+        // A huge, technically non-negative `high - low + 1` that would
+        // require allocating far more entries than `code` actually has
+        // bytes left for.
+        let mut code = vec![0xaa];
+        code.extend_from_slice(&[0; 3]);
+        code.extend_from_slice(&0i32.to_be_bytes()); // default
+        code.extend_from_slice(&0i32.to_be_bytes()); // low
+        code.extend_from_slice(&i32::MAX.to_be_bytes()); // high
+
+        assert!(matches!(decode(&code), Err(Error::MalformedSwitch(0))));
+    }
+
+    #[test]
+    fn rejects_lookupswitch_npairs_exceeding_remaining_bytes() {
+        let mut code = vec![0xab];
+        code.extend_from_slice(&[0; 3]);
+        code.extend_from_slice(&0i32.to_be_bytes()); // default
+        code.extend_from_slice(&i32::MAX.to_be_bytes()); // npairs
+
+        assert!(matches!(decode(&code), Err(Error::MalformedSwitch(0))));
+    }
+
+    #[test]
+    fn rejects_unknown_opcode() {
+        let code = vec![0xcb]; // not a valid opcode
+
+        assert!(matches!(
+            decode(&code),
+            Err(Error::UnknownOpcode { opcode: 0xcb, offset: 0 })
+        ));
+    }
+}
@@ -1,7 +1,8 @@
 use bitflags::bitflags;
-use byteorder::{BigEndian, ReadBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use crate::descriptor::{self, FieldType, MethodDescriptor};
 use crate::{Error, Result};
-use std::io::{Read, Cursor};
+use std::io::{Cursor, Read, Write};
 use std::path::PathBuf;
 
 #[derive(Debug)]
@@ -21,6 +22,11 @@ impl Version {
         }
     }
 
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>(self.minor)?;
+        w.write_u16::<BigEndian>(self.major)?;
+        Ok(())
+    }
 }
 
 pub enum ReferenceKind {
@@ -57,6 +63,11 @@ impl AccessFlags {
         let bits = r.read_u16::<BigEndian>()?;
         Self::from_bits(bits).ok_or(Error::InvalidAccessFlags(bits))
     }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>(self.bits())?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -110,19 +121,102 @@ pub enum Constant {
     },
     Package {
         name_index: usize,
+    },
+    // Fills the slot after a `Long`/`Double` entry (JVMS 4.4.5).
+    Unusable,
+}
+
+// Decodes one 1-, 2- or 3-byte Modified UTF-8 group, returning its code
+// point and the number of bytes it occupied.
+fn decode_modified_utf8_group(data: &[u8], i: usize) -> Result<(u32, usize)> {
+    let b0 = *data.get(i).ok_or(Error::InvalidModifiedUtf8)?;
+    if b0 != 0 && b0 & 0x80 == 0 {
+        Ok((b0 as u32, 1))
+    } else if b0 & 0xE0 == 0xC0 {
+        let b1 = *data.get(i + 1).ok_or(Error::InvalidModifiedUtf8)?;
+        if b1 & 0xC0 != 0x80 {
+            return Err(Error::InvalidModifiedUtf8);
+        }
+        let cp = ((b0 & 0x1F) as u32) << 6 | (b1 & 0x3F) as u32;
+        Ok((cp, 2))
+    } else if b0 & 0xF0 == 0xE0 {
+        let b1 = *data.get(i + 1).ok_or(Error::InvalidModifiedUtf8)?;
+        let b2 = *data.get(i + 2).ok_or(Error::InvalidModifiedUtf8)?;
+        if b1 & 0xC0 != 0x80 || b2 & 0xC0 != 0x80 {
+            return Err(Error::InvalidModifiedUtf8);
+        }
+        let cp = ((b0 & 0x0F) as u32) << 12 | ((b1 & 0x3F) as u32) << 6 | (b2 & 0x3F) as u32;
+        Ok((cp, 3))
+    } else {
+        Err(Error::InvalidModifiedUtf8)
     }
 }
 
+// Decodes a `CONSTANT_Utf8` byte buffer per JVMS 4.4.7.
+fn decode_modified_utf8(data: &[u8]) -> Result<String> {
+    let mut s = String::new();
+    let mut i = 0;
+    while i < data.len() {
+        let (cp, width) = decode_modified_utf8_group(data, i)?;
+        i += width;
+
+        if (0xD800..=0xDBFF).contains(&cp) {
+            let (lo, lo_width) = decode_modified_utf8_group(data, i)?;
+            if !(0xDC00..=0xDFFF).contains(&lo) {
+                return Err(Error::InvalidModifiedUtf8);
+            }
+            i += lo_width;
+
+            let combined = 0x10000 + ((cp - 0xD800) << 10) + (lo - 0xDC00);
+            s.push(char::from_u32(combined).ok_or(Error::InvalidModifiedUtf8)?);
+        } else {
+            s.push(char::from_u32(cp).ok_or(Error::InvalidModifiedUtf8)?);
+        }
+    }
+
+    Ok(s)
+}
+
+// Inverse of `decode_modified_utf8`.
+fn encode_modified_utf8(s: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    for c in s.chars() {
+        let cp = c as u32;
+        if cp == 0 {
+            data.extend_from_slice(&[0xC0, 0x80]);
+        } else if cp <= 0x7F {
+            data.push(cp as u8);
+        } else if cp <= 0x7FF {
+            data.push(0xC0 | (cp >> 6) as u8);
+            data.push(0x80 | (cp & 0x3F) as u8);
+        } else if cp <= 0xFFFF {
+            data.push(0xE0 | (cp >> 12) as u8);
+            data.push(0x80 | ((cp >> 6) & 0x3F) as u8);
+            data.push(0x80 | (cp & 0x3F) as u8);
+        } else {
+            let adjusted = cp - 0x10000;
+            let hi = 0xD800 + (adjusted >> 10);
+            let lo = 0xDC00 + (adjusted & 0x3FF);
+            for unit in [hi, lo] {
+                data.push(0xE0 | (unit >> 12) as u8);
+                data.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                data.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+
+    data
+}
+
 impl Constant {
     pub fn read<R: Read>(r: &mut R) -> Result<Self> {
         let tag = r.read_u8()?;
         let constant = match tag {
             1 => {
-                // FIXME: that's not how "Modified UTF-8" decoding works.
                 let length = r.read_u16::<BigEndian>()?.into();
                 let mut data = vec![0; length];
                 r.read_exact(&mut data)?;
-                let data = String::from_utf8(data).unwrap();
+                let data = decode_modified_utf8(&data)?;
                 Constant::Utf8 {
                     data,
                 }
@@ -179,6 +273,91 @@ impl Constant {
 
         Ok(constant)
     }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        match self {
+            Constant::Utf8 { data } => {
+                w.write_u8(1)?;
+                let data = encode_modified_utf8(data);
+                w.write_u16::<BigEndian>(data.len() as u16)?;
+                w.write_all(&data)?;
+            }
+            Constant::Integer(value) => {
+                w.write_u8(3)?;
+                w.write_u32::<BigEndian>(*value)?;
+            }
+            Constant::Float(value) => {
+                w.write_u8(4)?;
+                w.write_f32::<BigEndian>(*value)?;
+            }
+            Constant::Long(value) => {
+                w.write_u8(5)?;
+                w.write_u64::<BigEndian>(*value)?;
+            }
+            Constant::Double(value) => {
+                w.write_u8(6)?;
+                w.write_f64::<BigEndian>(*value)?;
+            }
+            Constant::Class { name_index } => {
+                w.write_u8(7)?;
+                w.write_u16::<BigEndian>(*name_index as u16)?;
+            }
+            Constant::String { string_index } => {
+                w.write_u8(8)?;
+                w.write_u16::<BigEndian>(*string_index as u16)?;
+            }
+            Constant::FieldRef { class_index, name_and_type_index } => {
+                w.write_u8(9)?;
+                w.write_u16::<BigEndian>(*class_index as u16)?;
+                w.write_u16::<BigEndian>(*name_and_type_index as u16)?;
+            }
+            Constant::MethodRef { class_index, name_and_type_index } => {
+                w.write_u8(10)?;
+                w.write_u16::<BigEndian>(*class_index as u16)?;
+                w.write_u16::<BigEndian>(*name_and_type_index as u16)?;
+            }
+            Constant::InterfaceMethodRef { class_index, name_and_type_index } => {
+                w.write_u8(11)?;
+                w.write_u16::<BigEndian>(*class_index as u16)?;
+                w.write_u16::<BigEndian>(*name_and_type_index as u16)?;
+            }
+            Constant::NameAndType { name_index, descriptor_index } => {
+                w.write_u8(12)?;
+                w.write_u16::<BigEndian>(*name_index as u16)?;
+                w.write_u16::<BigEndian>(*descriptor_index as u16)?;
+            }
+            Constant::MethodHandle { reference_kind, reference_index } => {
+                w.write_u8(15)?;
+                w.write_u16::<BigEndian>(*reference_index as u16)?;
+                w.write_u16::<BigEndian>(*reference_kind)?;
+            }
+            Constant::MethodType { descriptor_index } => {
+                w.write_u8(16)?;
+                w.write_u16::<BigEndian>(*descriptor_index as u16)?;
+            }
+            Constant::Dynamic { bootstrap_method_attr_index, name_and_type_index } => {
+                w.write_u8(17)?;
+                w.write_u16::<BigEndian>(*bootstrap_method_attr_index as u16)?;
+                w.write_u16::<BigEndian>(*name_and_type_index as u16)?;
+            }
+            Constant::InvokeDynamic { bootstrap_method_attr_index, name_and_type_index } => {
+                w.write_u8(18)?;
+                w.write_u16::<BigEndian>(*bootstrap_method_attr_index as u16)?;
+                w.write_u16::<BigEndian>(*name_and_type_index as u16)?;
+            }
+            Constant::Module { name_index } => {
+                w.write_u8(19)?;
+                w.write_u16::<BigEndian>(*name_index as u16)?;
+            }
+            Constant::Package { name_index } => {
+                w.write_u8(20)?;
+                w.write_u16::<BigEndian>(*name_index as u16)?;
+            }
+            Constant::Unusable => unreachable!("Unusable slots are skipped by ConstantPool::write"),
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -189,10 +368,17 @@ pub struct ConstantPool {
 impl ConstantPool {
     pub fn read<R: Read>(r: &mut R) -> Result<Self> {
         let constant_pool_count = r.read_u16::<BigEndian>()?;
+        if constant_pool_count == 0 {
+            return Err(Error::InvalidConstantPoolCount(constant_pool_count));
+        }
         let mut pool = Vec::new();
-        for _ in 0..constant_pool_count-1 {
+        while pool.len() < constant_pool_count as usize - 1 {
             let constant = Constant::read(r)?;
+            let takes_two_slots = matches!(constant, Constant::Long(_) | Constant::Double(_));
             pool.push(constant);
+            if takes_two_slots {
+                pool.push(Constant::Unusable);
+            }
         }
 
         Ok(Self {
@@ -209,6 +395,237 @@ impl ConstantPool {
             Err(Error::InvalidConstantPoolType)
         }
     }
+
+    pub fn get(&self, index: u16) -> Result<&Constant> {
+        if index == 0 {
+            return Err(Error::InvalidConstantPoolIndex);
+        }
+        self.pool
+            .get(index as usize - 1)
+            .ok_or(Error::InvalidConstantPoolIndex)
+    }
+
+    pub fn name_and_type(&self, index: u16) -> Result<(String, String)> {
+        if let Constant::NameAndType { name_index, descriptor_index } = self.get(index)? {
+            Ok((self.string(*name_index as u16)?, self.string(*descriptor_index as u16)?))
+        } else {
+            Err(Error::InvalidConstantPoolType)
+        }
+    }
+
+    pub fn method_ref(&self, index: u16) -> Result<(String, String, String)> {
+        if let Constant::MethodRef { class_index, name_and_type_index } = self.get(index)? {
+            let class_name = self.class_name(*class_index as u16)?;
+            let (name, descriptor) = self.name_and_type(*name_and_type_index as u16)?;
+            Ok((class_name, name, descriptor))
+        } else {
+            Err(Error::InvalidConstantPoolType)
+        }
+    }
+
+    pub fn class_name(&self, index: u16) -> Result<String> {
+        if index == 0 {
+            return Err(Error::InvalidConstantPoolIndex);
+        }
+        let pool_index = index as usize - 1;
+        let c = self.pool.get(pool_index).ok_or(Error::InvalidConstantPoolIndex)?;
+        if let Constant::Class { name_index } = c {
+            self.string(*name_index as u16)
+        } else {
+            Err(Error::InvalidConstantPoolType)
+        }
+    }
+
+    pub fn utf8_index(&self, name: &str) -> Result<u16> {
+        self.pool
+            .iter()
+            .position(|c| matches!(c, Constant::Utf8 { data } if data == name))
+            .map(|index| (index + 1) as u16)
+            .ok_or_else(|| Error::MissingUtf8Constant(name.to_owned()))
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>((self.pool.len() + 1) as u16)?;
+        for constant in &self.pool {
+            if matches!(constant, Constant::Unusable) {
+                continue;
+            }
+            constant.write(w)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn long_constant_occupies_two_slots() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&4u16.to_be_bytes());
+        data.push(5); // CONSTANT_Long
+        data.extend_from_slice(&42u64.to_be_bytes());
+        data.push(1); // CONSTANT_Utf8
+        data.extend_from_slice(&5u16.to_be_bytes());
+        data.extend_from_slice(b"hello");
+
+        let pool = ConstantPool::read(&mut Cursor::new(data)).unwrap();
+
+        assert!(matches!(pool.pool[0], Constant::Long(42)));
+        assert!(matches!(pool.pool[1], Constant::Unusable));
+        assert_eq!(pool.string(3).unwrap(), "hello");
+    }
+
+    #[test]
+    fn rejects_zero_constant_pool_count() {
+        let data = 0u16.to_be_bytes().to_vec();
+
+        let err = ConstantPool::read(&mut Cursor::new(data)).unwrap_err();
+
+        assert!(matches!(err, Error::InvalidConstantPoolCount(0)));
+    }
+
+    #[test]
+    fn class_name_rejects_zero_index() {
+        let pool = ConstantPool { pool: vec![Constant::Integer(1)] };
+
+        assert!(matches!(pool.class_name(0), Err(Error::InvalidConstantPoolIndex)));
+    }
+
+    #[test]
+    fn get_rejects_zero_index() {
+        let pool = ConstantPool { pool: vec![Constant::Integer(1)] };
+
+        assert!(matches!(pool.get(0), Err(Error::InvalidConstantPoolIndex)));
+    }
+
+    #[test]
+    fn round_trips_supplementary_code_point() {
+        let s = "hi \u{1F600}!";
+        let encoded = encode_modified_utf8(s);
+
+        assert_eq!(encoded.len(), "hi ".len() + 3 + 3 + "!".len());
+        assert_eq!(decode_modified_utf8(&encoded).unwrap(), s);
+    }
+
+    #[test]
+    fn decodes_embedded_nul() {
+        let encoded = encode_modified_utf8("a\0b");
+        assert_eq!(encoded, [b'a', 0xC0, 0x80, b'b']);
+        assert_eq!(decode_modified_utf8(&encoded).unwrap(), "a\0b");
+    }
+
+    #[test]
+    fn rejects_truncated_multibyte_sequence() {
+        assert!(matches!(decode_modified_utf8(&[0xC0]), Err(Error::InvalidModifiedUtf8)));
+    }
+
+    #[test]
+    fn rejects_unpaired_high_surrogate() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&[0xED, 0xA0, 0x80]); // high surrogate 0xD800
+        data.extend_from_slice(b"x"); // not a low surrogate
+        assert!(matches!(decode_modified_utf8(&data), Err(Error::InvalidModifiedUtf8)));
+    }
+
+    #[test]
+    fn parses_code_attribute_with_line_number_table() {
+        let mut pool_data = Vec::new();
+        pool_data.extend_from_slice(&3u16.to_be_bytes()); // constant_pool_count
+        pool_data.push(1); // #1 Utf8 "Code"
+        pool_data.extend_from_slice(&4u16.to_be_bytes());
+        pool_data.extend_from_slice(b"Code");
+        pool_data.push(1); // #2 Utf8 "LineNumberTable"
+        pool_data.extend_from_slice(&15u16.to_be_bytes());
+        pool_data.extend_from_slice(b"LineNumberTable");
+        let cp = ConstantPool::read(&mut Cursor::new(pool_data)).unwrap();
+
+        let mut line_number_table_info = Vec::new();
+        line_number_table_info.extend_from_slice(&1u16.to_be_bytes()); // line_number_table_length
+        line_number_table_info.extend_from_slice(&0u16.to_be_bytes()); // start_pc
+        line_number_table_info.extend_from_slice(&42u16.to_be_bytes()); // line_number
+
+        let mut nested_attribute = Vec::new();
+        nested_attribute.extend_from_slice(&2u16.to_be_bytes()); // name -> "LineNumberTable"
+        nested_attribute.extend_from_slice(&(line_number_table_info.len() as u32).to_be_bytes());
+        nested_attribute.extend_from_slice(&line_number_table_info);
+
+        let code: Vec<u8> = vec![0xb1]; // return
+        let mut code_info = Vec::new();
+        code_info.extend_from_slice(&1u16.to_be_bytes()); // max_stack
+        code_info.extend_from_slice(&1u16.to_be_bytes()); // max_locals
+        code_info.extend_from_slice(&(code.len() as u32).to_be_bytes());
+        code_info.extend_from_slice(&code);
+        code_info.extend_from_slice(&0u16.to_be_bytes()); // exception_table_count
+        code_info.extend_from_slice(&1u16.to_be_bytes()); // attributes_count
+        code_info.extend_from_slice(&nested_attribute);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&1u16.to_be_bytes()); // name -> "Code"
+        data.extend_from_slice(&(code_info.len() as u32).to_be_bytes());
+        data.extend_from_slice(&code_info);
+
+        let attribute = Attribute::read(&mut Cursor::new(data), &cp).unwrap();
+        assert_eq!(attribute.name(), "Code");
+
+        match attribute.data() {
+            AttributeData::Code { max_stack, max_locals, code, attributes, .. } => {
+                assert_eq!(*max_stack, 1);
+                assert_eq!(*max_locals, 1);
+                assert_eq!(code, &[0xb1]);
+                assert_eq!(attributes.len(), 1);
+
+                match attributes[0].data() {
+                    AttributeData::LineNumberTable(entries) => {
+                        assert_eq!(entries.len(), 1);
+                        assert_eq!(entries[0].start_pc, 0);
+                        assert_eq!(entries[0].line_number, 42);
+                    }
+                    other => panic!("expected LineNumberTable, got {:?}", other),
+                }
+            }
+            other => panic!("expected Code, got {:?}", other),
+        }
+    }
+
+    pub(crate) fn minimal_class_bytes() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0xCAFEBABEu32.to_be_bytes());
+        data.extend_from_slice(&0u16.to_be_bytes()); // minor
+        data.extend_from_slice(&52u16.to_be_bytes()); // major
+
+        data.extend_from_slice(&5u16.to_be_bytes()); // constant_pool_count
+        data.push(1); // #1 Utf8 "Test"
+        data.extend_from_slice(&4u16.to_be_bytes());
+        data.extend_from_slice(b"Test");
+        data.push(7); // #2 Class -> #1
+        data.extend_from_slice(&1u16.to_be_bytes());
+        data.push(1); // #3 Utf8 "java/lang/Object"
+        data.extend_from_slice(&16u16.to_be_bytes());
+        data.extend_from_slice(b"java/lang/Object");
+        data.push(7); // #4 Class -> #3
+        data.extend_from_slice(&3u16.to_be_bytes());
+
+        data.extend_from_slice(&0x0001u16.to_be_bytes()); // access_flags: PUBLIC
+        data.extend_from_slice(&2u16.to_be_bytes()); // this_class
+        data.extend_from_slice(&4u16.to_be_bytes()); // super_class
+        data.extend_from_slice(&0u16.to_be_bytes()); // interfaces_count
+        data.extend_from_slice(&0u16.to_be_bytes()); // fields_count
+        data.extend_from_slice(&0u16.to_be_bytes()); // methods_count
+        data.extend_from_slice(&0u16.to_be_bytes()); // attributes_count
+
+        data
+    }
+
+    #[test]
+    fn round_trip_through_bytes() {
+        let class = Class::from_bytes(&minimal_class_bytes()).unwrap();
+        let bytes = class.to_bytes().unwrap();
+        let round_tripped = Class::from_bytes(&bytes).unwrap();
+
+        assert_eq!(format!("{:?}", class), format!("{:?}", round_tripped));
+    }
 }
 
 fn read_vec<T, F, R>(r: &mut R, f: F) -> Result<Vec<T>> where
@@ -224,10 +641,140 @@ fn read_vec<T, F, R>(r: &mut R, f: F) -> Result<Vec<T>> where
     Ok(elements)
 }
 
+fn write_vec<T, F, W>(w: &mut W, items: &[T], f: F) -> Result<()> where
+    W: Write,
+    F: Fn(&mut W, &T) -> Result<()>
+{
+    w.write_u16::<BigEndian>(items.len() as u16)?;
+    for item in items {
+        f(w, item)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct ExceptionTableEntry {
+    pub(crate) start_pc: u16,
+    pub(crate) end_pc: u16,
+    pub(crate) handler_pc: u16,
+    pub(crate) catch_type: usize,
+}
+
+impl ExceptionTableEntry {
+    pub fn read<R: Read>(r: &mut R) -> Result<Self> {
+        Ok(ExceptionTableEntry {
+            start_pc: r.read_u16::<BigEndian>()?,
+            end_pc: r.read_u16::<BigEndian>()?,
+            handler_pc: r.read_u16::<BigEndian>()?,
+            catch_type: r.read_u16::<BigEndian>()?.into(),
+        })
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>(self.start_pc)?;
+        w.write_u16::<BigEndian>(self.end_pc)?;
+        w.write_u16::<BigEndian>(self.handler_pc)?;
+        w.write_u16::<BigEndian>(self.catch_type as u16)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct LineNumberEntry {
+    pub(crate) start_pc: u16,
+    pub(crate) line_number: u16,
+}
+
+impl LineNumberEntry {
+    pub fn read<R: Read>(r: &mut R) -> Result<Self> {
+        Ok(LineNumberEntry {
+            start_pc: r.read_u16::<BigEndian>()?,
+            line_number: r.read_u16::<BigEndian>()?,
+        })
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>(self.start_pc)?;
+        w.write_u16::<BigEndian>(self.line_number)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct BootstrapMethod {
+    pub(crate) method_ref: usize,
+    pub(crate) arguments: Vec<usize>,
+}
+
+impl BootstrapMethod {
+    pub fn read<R: Read>(r: &mut R) -> Result<Self> {
+        Ok(BootstrapMethod {
+            method_ref: r.read_u16::<BigEndian>()?.into(),
+            arguments: read_vec(r, |r| Ok(r.read_u16::<BigEndian>()?.into()))?,
+        })
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>(self.method_ref as u16)?;
+        write_vec(w, &self.arguments, |w, a| Ok(w.write_u16::<BigEndian>(*a as u16)?))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct InnerClassEntry {
+    pub(crate) inner_class_info_index: usize,
+    pub(crate) outer_class_info_index: usize,
+    pub(crate) inner_name_index: usize,
+    pub(crate) inner_class_access_flags: u16,
+}
+
+impl InnerClassEntry {
+    pub fn read<R: Read>(r: &mut R) -> Result<Self> {
+        Ok(InnerClassEntry {
+            inner_class_info_index: r.read_u16::<BigEndian>()?.into(),
+            outer_class_info_index: r.read_u16::<BigEndian>()?.into(),
+            inner_name_index: r.read_u16::<BigEndian>()?.into(),
+            inner_class_access_flags: r.read_u16::<BigEndian>()?,
+        })
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u16::<BigEndian>(self.inner_class_info_index as u16)?;
+        w.write_u16::<BigEndian>(self.outer_class_info_index as u16)?;
+        w.write_u16::<BigEndian>(self.inner_name_index as u16)?;
+        w.write_u16::<BigEndian>(self.inner_class_access_flags)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum AttributeData {
+    Code {
+        max_stack: u16,
+        max_locals: u16,
+        code: Vec<u8>,
+        exception_table: Vec<ExceptionTableEntry>,
+        attributes: Vec<Attribute>,
+    },
+    LineNumberTable(Vec<LineNumberEntry>),
+    SourceFile {
+        index: usize,
+    },
+    ConstantValue {
+        index: usize,
+    },
+    Exceptions(Vec<usize>),
+    BootstrapMethods(Vec<BootstrapMethod>),
+    InnerClasses(Vec<InnerClassEntry>),
+    Raw(Vec<u8>),
+}
+
 #[derive(Debug)]
 pub struct Attribute {
     name: String,
-    info: Vec<u8>,
+    data: AttributeData,
 }
 
 impl Attribute {
@@ -236,11 +783,98 @@ impl Attribute {
         let info_length = r.read_u32::<BigEndian>()? as usize;
         let mut info = vec![0; info_length];
         r.read_exact(&mut info)?;
+        let mut info = Cursor::new(info);
+
+        let data = match name.as_str() {
+            "Code" => {
+                let max_stack = info.read_u16::<BigEndian>()?;
+                let max_locals = info.read_u16::<BigEndian>()?;
+                let code_length = info.read_u32::<BigEndian>()? as usize;
+                let mut code = vec![0; code_length];
+                info.read_exact(&mut code)?;
+                AttributeData::Code {
+                    max_stack,
+                    max_locals,
+                    code,
+                    exception_table: read_vec(&mut info, ExceptionTableEntry::read)?,
+                    attributes: read_vec(&mut info, |r| Attribute::read(r, cp))?,
+                }
+            }
+            "LineNumberTable" => {
+                AttributeData::LineNumberTable(read_vec(&mut info, LineNumberEntry::read)?)
+            }
+            "SourceFile" => AttributeData::SourceFile {
+                index: info.read_u16::<BigEndian>()?.into(),
+            },
+            "ConstantValue" => AttributeData::ConstantValue {
+                index: info.read_u16::<BigEndian>()?.into(),
+            },
+            "Exceptions" => {
+                AttributeData::Exceptions(read_vec(&mut info, |r| Ok(r.read_u16::<BigEndian>()?.into()))?)
+            }
+            "BootstrapMethods" => {
+                AttributeData::BootstrapMethods(read_vec(&mut info, BootstrapMethod::read)?)
+            }
+            "InnerClasses" => {
+                AttributeData::InnerClasses(read_vec(&mut info, InnerClassEntry::read)?)
+            }
+            _ => AttributeData::Raw(info.into_inner()),
+        };
+
         Ok(Attribute {
             name,
-            info
+            data,
         })
     }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn data(&self) -> &AttributeData {
+        &self.data
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W, cp: &ConstantPool) -> Result<()> {
+        w.write_u16::<BigEndian>(cp.utf8_index(&self.name)?)?;
+
+        let mut info = Vec::new();
+        match &self.data {
+            AttributeData::Code { max_stack, max_locals, code, exception_table, attributes } => {
+                info.write_u16::<BigEndian>(*max_stack)?;
+                info.write_u16::<BigEndian>(*max_locals)?;
+                info.write_u32::<BigEndian>(code.len() as u32)?;
+                info.extend_from_slice(code);
+                write_vec(&mut info, exception_table, |w, e| e.write(w))?;
+                write_vec(&mut info, attributes, |w, a| a.write(w, cp))?;
+            }
+            AttributeData::LineNumberTable(entries) => {
+                write_vec(&mut info, entries, |w, e| e.write(w))?;
+            }
+            AttributeData::SourceFile { index } => {
+                info.write_u16::<BigEndian>(*index as u16)?;
+            }
+            AttributeData::ConstantValue { index } => {
+                info.write_u16::<BigEndian>(*index as u16)?;
+            }
+            AttributeData::Exceptions(indices) => {
+                write_vec(&mut info, indices, |w, i| Ok(w.write_u16::<BigEndian>(*i as u16)?))?;
+            }
+            AttributeData::BootstrapMethods(methods) => {
+                write_vec(&mut info, methods, |w, m| m.write(w))?;
+            }
+            AttributeData::InnerClasses(classes) => {
+                write_vec(&mut info, classes, |w, c| c.write(w))?;
+            }
+            AttributeData::Raw(data) => {
+                info.extend_from_slice(data);
+            }
+        }
+
+        w.write_u32::<BigEndian>(info.len() as u32)?;
+        w.write_all(&info)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -268,6 +902,18 @@ impl Field {
     pub fn descriptor(&self) -> &str {
         self.descriptor.as_str()
     }
+
+    pub fn parsed_descriptor(&self) -> Result<FieldType> {
+        descriptor::parse_field_descriptor(&self.descriptor)
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W, cp: &ConstantPool) -> Result<()> {
+        self.access_flags.write(w)?;
+        w.write_u16::<BigEndian>(cp.utf8_index(&self.name)?)?;
+        w.write_u16::<BigEndian>(cp.utf8_index(&self.descriptor)?)?;
+        write_vec(w, &self.attributes, |w, a| a.write(w, cp))?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -295,6 +941,22 @@ impl Method {
     pub fn descriptor(&self) -> &str {
         self.descriptor.as_str()
     }
+
+    pub fn parsed_descriptor(&self) -> Result<MethodDescriptor> {
+        descriptor::parse_method_descriptor(&self.descriptor)
+    }
+
+    pub fn code(&self) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.name() == "Code")
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W, cp: &ConstantPool) -> Result<()> {
+        self.access_flags.write(w)?;
+        w.write_u16::<BigEndian>(cp.utf8_index(&self.name)?)?;
+        w.write_u16::<BigEndian>(cp.utf8_index(&self.descriptor)?)?;
+        write_vec(w, &self.attributes, |w, a| a.write(w, cp))?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -354,4 +1016,60 @@ impl Class {
     pub fn method(&self, name: &str) -> Option<&Method> {
         self.methods.iter().find(|method| method.name() == name)
     }
+
+    pub fn constant_pool(&self) -> &ConstantPool {
+        &self.constant_pool
+    }
+
+    pub fn name(&self) -> Result<String> {
+        self.constant_pool.class_name(self.this_class)
+    }
+
+    pub fn super_class_name(&self) -> Result<Option<String>> {
+        if self.super_class == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(self.constant_pool.class_name(self.super_class)?))
+        }
+    }
+
+    pub fn interface_names(&self) -> Result<Vec<String>> {
+        self.interfaces
+            .iter()
+            .map(|&index| self.constant_pool.class_name(index))
+            .collect()
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_u32::<BigEndian>(0xCAFEBABE)?;
+        self.version.write(w)?;
+        self.constant_pool.write(w)?;
+        self.access_flags.write(w)?;
+        w.write_u16::<BigEndian>(self.this_class)?;
+        w.write_u16::<BigEndian>(self.super_class)?;
+        write_vec(w, &self.interfaces, |w, i| Ok(w.write_u16::<BigEndian>(*i)?))?;
+        write_vec(w, &self.fields, |w, f| f.write(w, &self.constant_pool))?;
+        write_vec(w, &self.methods, |w, m| m.write(w, &self.constant_pool))?;
+        write_vec(w, &self.attributes, |w, a| a.write(w, &self.constant_pool))?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.write(&mut data)?;
+        Ok(data)
+    }
+
+    pub fn invoke(
+        &self,
+        store: &mut crate::store::ClassStore,
+        method_name: &str,
+        args: Vec<crate::interpreter::Value>,
+    ) -> Result<Option<crate::interpreter::Value>> {
+        let method = self
+            .method(method_name)
+            .ok_or_else(|| Error::MethodNotFound(method_name.to_owned()))?;
+
+        crate::interpreter::Interpreter::new(store).run(self, method, args)
+    }
 }
\ No newline at end of file
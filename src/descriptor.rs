@@ -0,0 +1,140 @@
+use crate::{Error, Result};
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Byte,
+    Char,
+    Double,
+    Float,
+    Int,
+    Long,
+    Short,
+    Boolean,
+    Object(String),
+    Array(Box<FieldType>, usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReturnType {
+    Void,
+    Value(FieldType),
+}
+
+#[derive(Debug)]
+pub struct MethodDescriptor {
+    params: Vec<FieldType>,
+    return_type: ReturnType,
+}
+
+impl MethodDescriptor {
+    pub fn params(&self) -> &[FieldType] {
+        &self.params
+    }
+
+    pub fn return_type(&self) -> &ReturnType {
+        &self.return_type
+    }
+}
+
+fn parse_field_type(chars: &mut Peekable<Chars>, descriptor: &str) -> Result<FieldType> {
+    let c = chars
+        .next()
+        .ok_or_else(|| Error::InvalidDescriptor(descriptor.to_owned()))?;
+
+    let field_type = match c {
+        'B' => FieldType::Byte,
+        'C' => FieldType::Char,
+        'D' => FieldType::Double,
+        'F' => FieldType::Float,
+        'I' => FieldType::Int,
+        'J' => FieldType::Long,
+        'S' => FieldType::Short,
+        'Z' => FieldType::Boolean,
+        'L' => {
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some(';') => break,
+                    Some(c) => name.push(c),
+                    None => return Err(Error::InvalidDescriptor(descriptor.to_owned())),
+                }
+            }
+            FieldType::Object(name)
+        }
+        '[' => {
+            let mut dims = 1;
+            while chars.peek() == Some(&'[') {
+                chars.next();
+                dims += 1;
+            }
+            let element = parse_field_type(chars, descriptor)?;
+            FieldType::Array(Box::new(element), dims)
+        }
+        _ => return Err(Error::InvalidDescriptor(descriptor.to_owned())),
+    };
+
+    Ok(field_type)
+}
+
+// Parses a field descriptor as defined by JVMS 4.3.2, e.g. `I`, `[Ljava/lang/String;`.
+pub fn parse_field_descriptor(descriptor: &str) -> Result<FieldType> {
+    let mut chars = descriptor.chars().peekable();
+    let field_type = parse_field_type(&mut chars, descriptor)?;
+    if chars.next().is_some() {
+        return Err(Error::InvalidDescriptor(descriptor.to_owned()));
+    }
+
+    Ok(field_type)
+}
+
+// Parses a method descriptor as defined by JVMS 4.3.3, e.g. `([Ljava/lang/String;I)V`.
+pub fn parse_method_descriptor(descriptor: &str) -> Result<MethodDescriptor> {
+    let mut chars = descriptor.chars().peekable();
+
+    if chars.next() != Some('(') {
+        return Err(Error::InvalidDescriptor(descriptor.to_owned()));
+    }
+
+    let mut params = Vec::new();
+    while chars.peek() != Some(&')') {
+        params.push(parse_field_type(&mut chars, descriptor)?);
+    }
+    chars.next(); // ')'
+
+    let return_type = if chars.peek() == Some(&'V') {
+        chars.next();
+        ReturnType::Void
+    } else {
+        ReturnType::Value(parse_field_type(&mut chars, descriptor)?)
+    };
+
+    if chars.next().is_some() {
+        return Err(Error::InvalidDescriptor(descriptor.to_owned()));
+    }
+
+    Ok(MethodDescriptor {
+        params,
+        return_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_array_and_object_params() {
+        let descriptor = parse_method_descriptor("([Ljava/lang/String;I)V").unwrap();
+
+        assert_eq!(
+            descriptor.params(),
+            &[
+                FieldType::Array(Box::new(FieldType::Object("java/lang/String".to_owned())), 1),
+                FieldType::Int,
+            ]
+        );
+        assert_eq!(descriptor.return_type(), &ReturnType::Void);
+    }
+}
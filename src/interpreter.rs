@@ -0,0 +1,405 @@
+use crate::bytecode::{self, Instruction};
+use crate::class::{AttributeData, Class, Constant, Method};
+use crate::descriptor;
+use crate::store::ClassStore;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Value {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Reference(i32),
+}
+
+impl Value {
+    fn as_int(self) -> Result<i32> {
+        match self {
+            Value::Int(v) => Ok(v),
+            _ => Err(Error::TypeMismatch),
+        }
+    }
+}
+
+// A single method invocation's local-variable array and operand stack.
+// `Long`/`Double` locals conceptually occupy two consecutive slots per
+// JVMS 2.6.1; the second slot is never read directly so it's left holding
+// whatever default value occupied it.
+struct StackFrame {
+    locals: Vec<Value>,
+    stack: Vec<Value>,
+}
+
+impl StackFrame {
+    fn new(max_locals: u16) -> Self {
+        StackFrame {
+            locals: vec![Value::Int(0); max_locals as usize],
+            stack: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        self.stack.push(value);
+    }
+
+    fn pop(&mut self) -> Result<Value> {
+        self.stack.pop().ok_or(Error::OperandStackUnderflow)
+    }
+
+    fn pop_int(&mut self) -> Result<i32> {
+        self.pop()?.as_int()
+    }
+
+    fn load(&self, index: u16) -> Result<Value> {
+        self.locals
+            .get(index as usize)
+            .copied()
+            .ok_or(Error::InvalidLocalIndex(index))
+    }
+
+    fn store(&mut self, index: u16, value: Value) -> Result<()> {
+        let slot = self
+            .locals
+            .get_mut(index as usize)
+            .ok_or(Error::InvalidLocalIndex(index))?;
+        *slot = value;
+        Ok(())
+    }
+}
+
+// A minimal interpreter that steps through a single `Code` attribute's
+// decoded instructions. Covers just enough of the JVM instruction set to
+// run simple, branch-and-arithmetic-only `static` methods.
+pub struct Interpreter<'a> {
+    store: &'a mut ClassStore,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(store: &'a mut ClassStore) -> Self {
+        Interpreter { store }
+    }
+
+    pub fn run(&mut self, class: &Class, method: &Method, args: Vec<Value>) -> Result<Option<Value>> {
+        let code_attr = method
+            .code()
+            .ok_or_else(|| Error::MissingCodeAttribute(method.name().to_owned()))?;
+        let (max_locals, code) = match code_attr.data() {
+            AttributeData::Code { max_locals, code, .. } => (*max_locals, code),
+            _ => unreachable!("Method::code only ever returns a Code attribute"),
+        };
+
+        let instructions = bytecode::decode(code)?;
+        let offsets: Vec<u32> = instructions.iter().map(|(offset, _)| *offset).collect();
+
+        // Args are passed one `Value` per logical parameter, in source
+        // order; `Long`/`Double` still occupy two local slots each (JVMS
+        // 2.6.1), so the destination index has to skip ahead for them.
+        let mut frame = StackFrame::new(max_locals);
+        let mut index = 0u16;
+        for arg in args {
+            let takes_two_slots = matches!(arg, Value::Long(_) | Value::Double(_));
+            frame.store(index, arg)?;
+            index += if takes_two_slots { 2 } else { 1 };
+        }
+
+        let mut pc = 0usize;
+        loop {
+            if pc >= instructions.len() {
+                return Err(Error::FellOffCode(method.name().to_owned()));
+            }
+            let (offset, instruction) = &instructions[pc];
+            let offset = *offset;
+
+            match instruction {
+                Instruction::Nop => {}
+                Instruction::IconstM1 => frame.push(Value::Int(-1)),
+                Instruction::Iconst0 => frame.push(Value::Int(0)),
+                Instruction::Iconst1 => frame.push(Value::Int(1)),
+                Instruction::Iconst2 => frame.push(Value::Int(2)),
+                Instruction::Iconst3 => frame.push(Value::Int(3)),
+                Instruction::Iconst4 => frame.push(Value::Int(4)),
+                Instruction::Iconst5 => frame.push(Value::Int(5)),
+                Instruction::Bipush(value) => frame.push(Value::Int(*value as i32)),
+                Instruction::Sipush(value) => frame.push(Value::Int(*value as i32)),
+                Instruction::Ldc(index) | Instruction::LdcW(index) => {
+                    match class.constant_pool().get(*index as u16)? {
+                        Constant::Integer(value) => frame.push(Value::Int(*value as i32)),
+                        Constant::Float(value) => frame.push(Value::Float(*value)),
+                        _ => return Err(Error::UnsupportedInstruction(format!("{:?}", instruction))),
+                    }
+                }
+                Instruction::Ldc2W(index) => match class.constant_pool().get(*index as u16)? {
+                    Constant::Long(value) => frame.push(Value::Long(*value as i64)),
+                    Constant::Double(value) => frame.push(Value::Double(*value)),
+                    _ => return Err(Error::UnsupportedInstruction(format!("{:?}", instruction))),
+                },
+                Instruction::Iload(index) => frame.push(frame.load(*index)?),
+                Instruction::Iload0 => frame.push(frame.load(0)?),
+                Instruction::Iload1 => frame.push(frame.load(1)?),
+                Instruction::Iload2 => frame.push(frame.load(2)?),
+                Instruction::Iload3 => frame.push(frame.load(3)?),
+                Instruction::Istore(index) => {
+                    let value = frame.pop()?;
+                    frame.store(*index, value)?;
+                }
+                Instruction::Istore0 => {
+                    let value = frame.pop()?;
+                    frame.store(0, value)?;
+                }
+                Instruction::Istore1 => {
+                    let value = frame.pop()?;
+                    frame.store(1, value)?;
+                }
+                Instruction::Istore2 => {
+                    let value = frame.pop()?;
+                    frame.store(2, value)?;
+                }
+                Instruction::Istore3 => {
+                    let value = frame.pop()?;
+                    frame.store(3, value)?;
+                }
+                Instruction::Iadd => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    frame.push(Value::Int(a.wrapping_add(b)));
+                }
+                Instruction::Isub => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    frame.push(Value::Int(a.wrapping_sub(b)));
+                }
+                Instruction::Imul => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    frame.push(Value::Int(a.wrapping_mul(b)));
+                }
+                Instruction::IfIcmpeq(delta) => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    if a == b {
+                        pc = Self::branch_index(&offsets, offset, *delta)?;
+                        continue;
+                    }
+                }
+                Instruction::IfIcmpne(delta) => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    if a != b {
+                        pc = Self::branch_index(&offsets, offset, *delta)?;
+                        continue;
+                    }
+                }
+                Instruction::IfIcmplt(delta) => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    if a < b {
+                        pc = Self::branch_index(&offsets, offset, *delta)?;
+                        continue;
+                    }
+                }
+                Instruction::IfIcmpge(delta) => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    if a >= b {
+                        pc = Self::branch_index(&offsets, offset, *delta)?;
+                        continue;
+                    }
+                }
+                Instruction::IfIcmpgt(delta) => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    if a > b {
+                        pc = Self::branch_index(&offsets, offset, *delta)?;
+                        continue;
+                    }
+                }
+                Instruction::IfIcmple(delta) => {
+                    let b = frame.pop_int()?;
+                    let a = frame.pop_int()?;
+                    if a <= b {
+                        pc = Self::branch_index(&offsets, offset, *delta)?;
+                        continue;
+                    }
+                }
+                Instruction::Goto(delta) => {
+                    pc = Self::branch_index(&offsets, offset, *delta)?;
+                    continue;
+                }
+                Instruction::Return => return Ok(None),
+                Instruction::Ireturn => return Ok(Some(Value::Int(frame.pop_int()?))),
+                Instruction::InvokeStatic(index) => {
+                    let (class_name, method_name, descriptor) =
+                        class.constant_pool().method_ref(*index as u16)?;
+                    let parsed_descriptor = descriptor::parse_method_descriptor(&descriptor)?;
+
+                    let arg_count = parsed_descriptor.params().len();
+                    let mut call_args = Vec::with_capacity(arg_count);
+                    for _ in 0..arg_count {
+                        call_args.push(frame.pop()?);
+                    }
+                    call_args.reverse();
+
+                    let target_class = self.store.get(&class_name)?;
+                    let target_method = target_class
+                        .method(&method_name)
+                        .ok_or_else(|| Error::MethodNotFound(method_name.clone()))?;
+
+                    let result = Interpreter::new(self.store).run(&target_class, target_method, call_args)?;
+                    if let Some(value) = result {
+                        frame.push(value);
+                    }
+                }
+                other => return Err(Error::UnsupportedInstruction(format!("{:?}", other))),
+            }
+
+            pc += 1;
+        }
+    }
+
+    // Resolves a branch's signed offset (relative to the branching
+    // instruction's own position, per JVMS 6.5) to an index into the
+    // decoded instruction list.
+    fn branch_index(offsets: &[u32], instruction_offset: u32, delta: i16) -> Result<usize> {
+        let target = (instruction_offset as i32 + delta as i32) as u32;
+        offsets
+            .binary_search(&target)
+            .map_err(|_| Error::InvalidBranchTarget(target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::class::Class;
+    use crate::store::ClassStore;
+
+    // Builds a minimal `Test extends java.lang.Object` class with a single
+    // static method named `method_name` taking the given descriptor, backed by a
+    // `Code` attribute holding `code`.
+    fn class_with_method(method_name: &str, descriptor: &str, max_locals: u16, code: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0xCAFEBABEu32.to_be_bytes());
+        data.extend_from_slice(&0u16.to_be_bytes());
+        data.extend_from_slice(&52u16.to_be_bytes());
+
+        data.extend_from_slice(&8u16.to_be_bytes()); // constant_pool_count
+        data.push(1); // #1 Utf8 "Test"
+        data.extend_from_slice(&4u16.to_be_bytes());
+        data.extend_from_slice(b"Test");
+        data.push(7); // #2 Class -> #1
+        data.extend_from_slice(&1u16.to_be_bytes());
+        data.push(1); // #3 Utf8 "java/lang/Object"
+        data.extend_from_slice(&16u16.to_be_bytes());
+        data.extend_from_slice(b"java/lang/Object");
+        data.push(7); // #4 Class -> #3
+        data.extend_from_slice(&3u16.to_be_bytes());
+        data.push(1); // #5 Utf8 method name
+        data.extend_from_slice(&(method_name.len() as u16).to_be_bytes());
+        data.extend_from_slice(method_name.as_bytes());
+        data.push(1); // #6 Utf8 descriptor
+        data.extend_from_slice(&(descriptor.len() as u16).to_be_bytes());
+        data.extend_from_slice(descriptor.as_bytes());
+        data.push(1); // #7 Utf8 "Code"
+        data.extend_from_slice(&4u16.to_be_bytes());
+        data.extend_from_slice(b"Code");
+
+        data.extend_from_slice(&0x0001u16.to_be_bytes()); // access_flags: PUBLIC
+        data.extend_from_slice(&2u16.to_be_bytes()); // this_class
+        data.extend_from_slice(&4u16.to_be_bytes()); // super_class
+        data.extend_from_slice(&0u16.to_be_bytes()); // interfaces_count
+        data.extend_from_slice(&0u16.to_be_bytes()); // fields_count
+
+        data.extend_from_slice(&1u16.to_be_bytes()); // methods_count
+        data.extend_from_slice(&0x0009u16.to_be_bytes()); // PUBLIC | STATIC
+        data.extend_from_slice(&5u16.to_be_bytes()); // name -> #5
+        data.extend_from_slice(&6u16.to_be_bytes()); // descriptor -> #6
+        data.extend_from_slice(&1u16.to_be_bytes()); // attributes_count
+        data.extend_from_slice(&7u16.to_be_bytes()); // attribute name -> "Code"
+
+        let mut info = Vec::new();
+        info.extend_from_slice(&4u16.to_be_bytes()); // max_stack
+        info.extend_from_slice(&max_locals.to_be_bytes());
+        info.extend_from_slice(&(code.len() as u32).to_be_bytes());
+        info.extend_from_slice(code);
+        info.extend_from_slice(&0u16.to_be_bytes()); // exception_table_count
+        info.extend_from_slice(&0u16.to_be_bytes()); // attributes_count
+        data.extend_from_slice(&(info.len() as u32).to_be_bytes());
+        data.extend_from_slice(&info);
+
+        data.extend_from_slice(&0u16.to_be_bytes()); // class attributes_count
+
+        data
+    }
+
+    // `sum(n)` computed via a loop (`if_icmpge`/`goto`) rather than straight-
+    // line arithmetic, so the branch-handling paths get exercised too:
+    // local0 = n, local1 = sum, local2 = i.
+    #[test]
+    fn runs_looping_sum_method() {
+        #[rustfmt::skip]
+        let code: Vec<u8> = vec![
+            0x03,                   // iconst_0
+            0x3c,                   // istore_1 (sum = 0)
+            0x03,                   // iconst_0
+            0x3d,                   // istore_2 (i = 0)
+            // loop:
+            0x1c,                   // iload_2
+            0x1a,                   // iload_0
+            0xa2, 0x00, 0x0e,       // if_icmpge +14 -> end
+            0x1b,                   // iload_1
+            0x1c,                   // iload_2
+            0x60,                   // iadd
+            0x3c,                   // istore_1 (sum += i)
+            0x1c,                   // iload_2
+            0x04,                   // iconst_1
+            0x60,                   // iadd
+            0x3d,                   // istore_2 (i += 1)
+            0xa7, 0xff, 0xf3,       // goto -13 -> loop
+            // end:
+            0x1b,                   // iload_1
+            0xac,                   // ireturn
+        ];
+
+        let class = Class::from_bytes(&class_with_method("sum", "(I)I", 3, &code)).unwrap();
+        let mut store = ClassStore::new();
+        let result = class.invoke(&mut store, "sum", vec![Value::Int(5)]).unwrap();
+
+        match result {
+            Some(Value::Int(v)) => assert_eq!(v, 10),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    // A method whose `Code` never reaches a `return`/`ireturn` should be
+    // reported as a typed error, not panic by indexing past the last
+    // decoded instruction.
+    #[test]
+    fn fell_off_code_is_an_error() {
+        let code: Vec<u8> = vec![0x00]; // nop, no terminating instruction
+
+        let class = Class::from_bytes(&class_with_method("broken", "()V", 0, &code)).unwrap();
+        let mut store = ClassStore::new();
+        let err = class.invoke(&mut store, "broken", vec![]).unwrap_err();
+
+        assert!(matches!(err, Error::FellOffCode(_)));
+    }
+
+    // A `Long` arg occupies two local slots (JVMS 2.6.1), so the `int` arg
+    // after it must land at local index 2, not 1.
+    #[test]
+    fn long_arg_reserves_two_local_slots() {
+        let code: Vec<u8> = vec![0x1c, 0xac]; // iload_2, ireturn
+
+        let class = Class::from_bytes(&class_with_method("method", "(JI)I", 3, &code)).unwrap();
+        let mut store = ClassStore::new();
+        let result = class
+            .invoke(&mut store, "method", vec![Value::Long(5), Value::Int(10)])
+            .unwrap();
+
+        match result {
+            Some(Value::Int(v)) => assert_eq!(v, 10),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
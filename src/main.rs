@@ -1,6 +1,10 @@
 #![allow(dead_code)]
 
+mod bytecode;
 mod class;
+mod descriptor;
+mod interpreter;
+mod store;
 
 use class::Class;
 
@@ -21,11 +25,62 @@ pub enum Error {
     #[error("Invalid access flags: 0x{0:04X}")]
     InvalidAccessFlags(u16),
 
+    #[error("Invalid constant pool count: {0}")]
+    InvalidConstantPoolCount(u16),
+
     #[error("Invalid constant pool index")]
     InvalidConstantPoolIndex,
 
     #[error("Invalid constant pool type")]
     InvalidConstantPoolType,
+
+    #[error("Invalid Modified UTF-8 data")]
+    InvalidModifiedUtf8,
+
+    #[error("Unknown opcode 0x{opcode:02X} at offset {offset}")]
+    UnknownOpcode { opcode: u8, offset: u32 },
+
+    #[error("Truncated bytecode at offset {0}")]
+    TruncatedBytecode(u32),
+
+    #[error("Invalid descriptor: {0}")]
+    InvalidDescriptor(String),
+
+    #[error("No Utf8 constant found for {0:?}")]
+    MissingUtf8Constant(String),
+
+    #[error("Class not found: {0}")]
+    ClassNotFound(String),
+
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("Operand stack underflow")]
+    OperandStackUnderflow,
+
+    #[error("Operand type mismatch")]
+    TypeMismatch,
+
+    #[error("Invalid local variable index: {0}")]
+    InvalidLocalIndex(u16),
+
+    #[error("Invalid branch target: {0}")]
+    InvalidBranchTarget(u32),
+
+    #[error("Method not found: {0}")]
+    MethodNotFound(String),
+
+    #[error("Method {0} has no Code attribute")]
+    MissingCodeAttribute(String),
+
+    #[error("Unsupported instruction: {0}")]
+    UnsupportedInstruction(String),
+
+    #[error("Malformed switch instruction at offset {0}")]
+    MalformedSwitch(u32),
+
+    #[error("Execution of {0} fell off the end of its Code without returning")]
+    FellOffCode(String),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
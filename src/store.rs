@@ -0,0 +1,137 @@
+use crate::class::Class;
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+enum Source {
+    Directory(PathBuf),
+    Jar(PathBuf),
+}
+
+// Resolves fully-qualified class names (e.g. "java/lang/Object") to parsed
+// `Class` values, lazily loading them from a directory tree or `.jar`
+// archive and caching the result so repeated lookups are free. Classes are
+// cached behind `Rc` rather than returned by reference so that resolving
+// one class (e.g. a superclass, or an `invokestatic` target) doesn't keep
+// the store borrowed while that lookup is still in scope.
+pub struct ClassStore {
+    sources: Vec<Source>,
+    classes: HashMap<String, Rc<Class>>,
+}
+
+impl ClassStore {
+    pub fn new() -> Self {
+        ClassStore {
+            sources: Vec::new(),
+            classes: HashMap::new(),
+        }
+    }
+
+    pub fn add_directory(&mut self, path: impl Into<PathBuf>) {
+        self.sources.push(Source::Directory(path.into()));
+    }
+
+    pub fn add_jar(&mut self, path: impl Into<PathBuf>) {
+        self.sources.push(Source::Jar(path.into()));
+    }
+
+    pub fn get(&mut self, name: &str) -> Result<Rc<Class>> {
+        if !self.classes.contains_key(name) {
+            let class = self.load(name)?;
+            self.classes.insert(name.to_owned(), Rc::new(class));
+        }
+
+        Ok(Rc::clone(self.classes.get(name).expect("just inserted")))
+    }
+
+    fn load(&self, name: &str) -> Result<Class> {
+        let internal_name = name.replace('.', "/");
+
+        for source in &self.sources {
+            match source {
+                Source::Directory(dir) => {
+                    let mut path = dir.clone();
+                    for part in internal_name.split('/') {
+                        path.push(part);
+                    }
+                    path.set_extension("class");
+
+                    if path.is_file() {
+                        return Class::from_bytes(&std::fs::read(path)?);
+                    }
+                }
+                Source::Jar(jar_path) => {
+                    let file = File::open(jar_path)?;
+                    let mut archive = zip::ZipArchive::new(file)?;
+                    let entry_name = format!("{}.class", internal_name);
+
+                    let data = match archive.by_name(&entry_name) {
+                        Ok(mut entry) => {
+                            let mut data = Vec::new();
+                            entry.read_to_end(&mut data)?;
+                            Some(data)
+                        }
+                        Err(zip::result::ZipError::FileNotFound) => None,
+                        Err(err) => return Err(err.into()),
+                    };
+
+                    if let Some(data) = data {
+                        return Class::from_bytes(&data);
+                    }
+                }
+            }
+        }
+
+        Err(Error::ClassNotFound(name.to_owned()))
+    }
+}
+
+impl Default for ClassStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::class::tests::minimal_class_bytes;
+    use std::io::Write as _;
+
+    #[test]
+    fn loads_class_from_directory() {
+        let dir = std::env::temp_dir().join(format!("lyre-store-test-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Test.class"), minimal_class_bytes()).unwrap();
+
+        let mut store = ClassStore::new();
+        store.add_directory(dir.clone());
+        let class = store.get("Test").unwrap();
+
+        assert_eq!(class.name().unwrap(), "Test");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn loads_class_from_jar() {
+        let jar_path = std::env::temp_dir().join(format!("lyre-store-test-{}.jar", std::process::id()));
+        let file = File::create(&jar_path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        zip.start_file("Test.class", zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(&minimal_class_bytes()).unwrap();
+        zip.finish().unwrap();
+
+        let mut store = ClassStore::new();
+        store.add_jar(jar_path.clone());
+        let class = store.get("Test").unwrap();
+
+        assert_eq!(class.name().unwrap(), "Test");
+
+        std::fs::remove_file(&jar_path).unwrap();
+    }
+}